@@ -1,18 +1,55 @@
 use crate::prelude::*;
 use crate::unit::{CompositeUnit, CompositeUnitClass, Unit, UnitClass};
-use crate::util::{ItemStorage, StorageHolder};
-use std::collections::HashMap;
+use crate::util::{Id, ItemStorage, StorageHolder};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 
 #[derive(Debug)]
 pub struct Environment {
     unit_classes: ItemStorage<UnitClass>,
     units: ItemStorage<Unit>,
     global_symbols: HashMap<Symbol, Value>,
+    /// Maps the dimensional signature of a derived unit (e.g. Mass^1 Length^1 Time^-2) to the
+    /// unit that should be used to display it (e.g. Newton), so formatted output can collapse
+    /// back into a friendly name instead of always printing the expanded product.
+    derived_units: HashMap<CompositeUnitClass, Id<Unit>>,
+    /// Style used by `format_value_detailed`/`format_formula_detailed`; changed with
+    /// `set_formatting_style`.
+    formatting_style: FormattingStyle,
+    /// Number base used for the same; changed with `set_base`.
+    base: Base,
+}
+
+/// How a scalar's numeric value should be rendered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FormattingStyle {
+    /// Scientific notation, unless `base` is non-decimal, in which case the value is rendered as
+    /// an integer in that base.
+    Auto,
+    /// The exact value as a fraction, e.g. `1/3`.
+    ExactFraction,
+    /// Fixed-point decimal notation, e.g. `1500.0`.
+    Decimal,
+    /// `{:e}` scientific notation, e.g. `1.5e3`.
+    Scientific,
+    /// Scientific notation whose exponent is a multiple of three, paired with the matching SI
+    /// prefix so the unit reads naturally, e.g. `1.5 kW` instead of `1.5e3 W`.
+    Engineering,
+}
+
+/// Number base used to render a scalar's value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
 }
 
 /// Stuff to format values.
 impl Environment {
-    pub fn format_base_unit(&self, base_unit: &CompositeUnitClass) -> String {
+    /// Prints the expanded product of fundamental unit classes, e.g. `Mass^1 / Time^2`, with no
+    /// attempt at collapsing it into a named derived unit.
+    fn format_base_unit_expanded(&self, base_unit: &CompositeUnitClass) -> String {
         let mut numerator = "".to_owned();
         let mut denominator = "".to_owned();
         for (unit_class_id, power) in base_unit.components.iter() {
@@ -26,6 +63,19 @@ impl Environment {
         format!("{} / {}", numerator, denominator)
     }
 
+    /// Prints `base_unit`, collapsing it into the name of a registered derived unit (e.g. `N`
+    /// instead of `Mass^1 Length^1 / Time^2`) when its signature exactly matches one.
+    pub fn format_base_unit(&self, base_unit: &CompositeUnitClass) -> String {
+        if let Some(unit_id) = self.derived_units.get(base_unit) {
+            return self.borrow(*unit_id).name.clone();
+        }
+        self.format_base_unit_expanded(base_unit)
+    }
+
+    /// Prints the unit components the caller actually selected as `unit`'s `display_unit`, e.g.
+    /// `cal` or `eV`, never substituting a different named unit of the same dimension (that's
+    /// what `format_base_unit` is for) — doing so here would print a value converted to calories
+    /// but labeled "joule".
     pub fn format_unit(&self, unit: &CompositeUnit) -> String {
         let mut numerator = "".to_owned();
         let mut denominator = "".to_owned();
@@ -41,16 +91,58 @@ impl Environment {
     }
 
     pub fn format_scalar_detailed(&self, scalar: &Scalar) -> String {
-        let ratio = self.base_conversion_ratio_of(&scalar.display_unit);
         assert!(scalar.precision > 0);
-        format!(
-            "{1:.0$e} {2} ({3:.0$e} {4})",
-            scalar.precision as usize - 1,
-            scalar.base_value / ratio,
-            self.format_unit(&scalar.display_unit),
-            scalar.base_value,
-            self.format_base_unit(&scalar.base_unit)
-        )
+        let ratio = self.base_conversion_ratio_of(&scalar.display_unit);
+        let (displayed_value, displayed_uncertainty) =
+            if let Some(log) = self.log_conversion_of(&scalar.display_unit) {
+                (log.from_base(scalar.base_value), None)
+            } else if scalar.uncertainty > 0.0 {
+                (
+                    scalar.base_value / ratio,
+                    Some(scalar.uncertainty / ratio),
+                )
+            } else {
+                (scalar.base_value / ratio, None)
+            };
+        let precision = displayed_uncertainty
+            .map(|uncertainty| significant_figure_precision(displayed_value, uncertainty))
+            .unwrap_or(scalar.precision);
+
+        // A logarithmic or uncertain display always renders in plain scientific notation,
+        // regardless of the configured style: engineering's prefix-collapsing and fraction/base
+        // rendering don't mix cleanly with a non-multiplicative scale or an explicit +/- term.
+        let (value_str, unit_str) = if displayed_uncertainty.is_some()
+            || self.log_conversion_of(&scalar.display_unit).is_some()
+        {
+            (
+                format!("{:.*e}", (precision.max(1) - 1) as usize, displayed_value),
+                self.format_unit(&scalar.display_unit),
+            )
+        } else {
+            self.render_scalar_number_and_unit(displayed_value, precision, &scalar.display_unit)
+        };
+
+        let base_value_decimals = (precision.max(1) - 1) as usize;
+        match displayed_uncertainty {
+            Some(uncertainty) => format!(
+                "{value} +/- {uncertainty:.decimals$e} {unit} ({base_value:.decimals$e} +/- {base_uncertainty:.decimals$e} {base_unit})",
+                value = value_str,
+                uncertainty = uncertainty,
+                decimals = base_value_decimals,
+                unit = unit_str,
+                base_value = scalar.base_value,
+                base_uncertainty = scalar.uncertainty,
+                base_unit = self.format_base_unit(&scalar.base_unit)
+            ),
+            None => format!(
+                "{value} {unit} ({base_value:.decimals$e} {base_unit})",
+                value = value_str,
+                unit = unit_str,
+                decimals = base_value_decimals,
+                base_value = scalar.base_value,
+                base_unit = self.format_base_unit(&scalar.base_unit)
+            ),
+        }
     }
 
     pub fn format_value_detailed(&self, value: &Value) -> String {
@@ -88,12 +180,28 @@ impl Environment {
             unit_classes: ItemStorage::new(),
             units: ItemStorage::new(),
             global_symbols: HashMap::new(),
+            derived_units: HashMap::new(),
+            formatting_style: FormattingStyle::Auto,
+            base: Base::Decimal,
         };
         crate::unit::add_default_units(&mut result);
         crate::constants::add_default_symbols(&mut result);
+        result.register_derived_units();
         result
     }
 
+    /// Populates `derived_units` with the signatures of the named units that were just added by
+    /// `add_default_units`, so the formatters can collapse a computed dimensional signature back
+    /// into its friendly name (e.g. force -> N, pressure -> Pa, energy -> J, power -> W, charge
+    /// -> C) instead of always printing the expanded product of fundamental unit classes.
+    fn register_derived_units(&mut self) {
+        for (unit_id, unit) in self.units.iter() {
+            if unit.is_named_derived_unit {
+                self.derived_units.insert(unit.base_class.clone(), unit_id);
+            }
+        }
+    }
+
     /// Returns the base unit of the given unit. For example, Meters^2*Seconds^-1 will return
     /// Length^2*Time^-1. Hz*Area^-1 will return Time^-1*Length^-2.
     pub fn base_unit_of(&self, unit: &CompositeUnit) -> CompositeUnitClass {
@@ -128,16 +236,109 @@ impl Environment {
         ratio
     }
 
+    /// Returns the logarithmic conversion descriptor of `unit`, if it is a bare logarithmic unit
+    /// such as `dB` or `Np` (a single component raised to the power of one). Units like dB don't
+    /// convert to the base unit by a simple multiplicative `base_ratio`, so `make_scalar` and
+    /// `format_scalar_detailed` consult this instead when it's present.
+    fn log_conversion_of(&self, unit: &CompositeUnit) -> Option<LogConversion> {
+        match unit.components.as_slice() {
+            [(unit_id, 1)] => self.borrow(*unit_id).log_conversion,
+            _ => None,
+        }
+    }
+
+    /// Renders `value` and `unit`'s name according to `self.base`/`self.formatting_style`.
+    fn render_scalar_number_and_unit(
+        &self,
+        value: f64,
+        precision: u32,
+        unit: &CompositeUnit,
+    ) -> (String, String) {
+        if self.base != Base::Decimal {
+            return (format_integer_in_base(value, self.base), self.format_unit(unit));
+        }
+        let decimals = (precision.max(1) - 1) as usize;
+        match self.formatting_style {
+            FormattingStyle::Decimal => (
+                format!("{:.*}", decimal_places(value, precision), value),
+                self.format_unit(unit),
+            ),
+            FormattingStyle::ExactFraction => (format_exact_fraction(value), self.format_unit(unit)),
+            FormattingStyle::Engineering => self.render_engineering(value, precision, unit),
+            FormattingStyle::Auto | FormattingStyle::Scientific => {
+                (format!("{:.*e}", decimals, value), self.format_unit(unit))
+            }
+        }
+    }
+
+    /// Renders `value` in engineering notation: the exponent is pulled down to the multiple of
+    /// three at or below it and folded into an SI prefix on `unit`'s name (e.g. `1.5 kW`), falling back to
+    /// plain scientific notation when no matching prefix exists or `unit` isn't a single named
+    /// unit.
+    fn render_engineering(&self, value: f64, precision: u32, unit: &CompositeUnit) -> (String, String) {
+        let decimals = (precision.max(1) - 1) as usize;
+        let exponent = engineering_exponent(value);
+        if exponent == 0 {
+            return (format!("{:.*}", decimals, value), self.format_unit(unit));
+        }
+        let named_unit = match unit.components.as_slice() {
+            [(unit_id, 1)] => Some(self.borrow(*unit_id).name.clone()),
+            _ => None,
+        };
+        match (named_unit, prefix_for_exponent(exponent)) {
+            (Some(name), Some(prefix)) => (
+                format!("{:.*}", decimals, value / 10f64.powi(exponent)),
+                format!("{}{}", prefix.symbol, name),
+            ),
+            _ => (format!("{:.*e}", decimals, value), self.format_unit(unit)),
+        }
+    }
+
     pub fn make_scalar(&self, value: f64, unit: CompositeUnit, precision: u32) -> Scalar {
+        self.make_scalar_with_uncertainty(value, unit, precision, 0.0)
+    }
+
+    /// Like `make_scalar`, but also records an absolute standard uncertainty (in `value`'s
+    /// units, not yet converted to the base unit), e.g. `5.0 +/- 0.1` meters. Pass `0.0` for an
+    /// exact value.
+    pub fn make_scalar_with_uncertainty(
+        &self,
+        value: f64,
+        unit: CompositeUnit,
+        precision: u32,
+        uncertainty: f64,
+    ) -> Scalar {
         let base_unit = self.base_unit_of(&unit);
-        let base_value = value * self.base_conversion_ratio_of(&unit);
-        Scalar::new(base_value, base_unit, unit, precision)
+        let ratio_or_log = self.log_conversion_of(&unit);
+        let base_value = match ratio_or_log {
+            Some(log) => log.to_base(value),
+            None => value * self.base_conversion_ratio_of(&unit),
+        };
+        let base_uncertainty = match ratio_or_log {
+            // A logarithmic unit's conversion isn't linear, so there's no single ratio to scale
+            // the uncertainty by; approximate it with the local slope of `to_base` at `value`.
+            Some(log) => {
+                let step = uncertainty.max(f64::EPSILON);
+                ((log.to_base(value + step) - log.to_base(value - step)) / (2.0 * step)).abs()
+                    * uncertainty
+            }
+            None => uncertainty * self.base_conversion_ratio_of(&unit),
+        };
+        Scalar::new_with_uncertainty(base_value, base_uncertainty, base_unit, unit, precision)
     }
 
     pub fn add_global_symbol(&mut self, symbol: Symbol, value: Value) {
         self.global_symbols.insert(symbol, value);
     }
 
+    pub fn set_formatting_style(&mut self, style: FormattingStyle) {
+        self.formatting_style = style;
+    }
+
+    pub fn set_base(&mut self, base: Base) {
+        self.base = base;
+    }
+
     pub fn borrow_global_symbols(&self) -> SymbolTable<'_> {
         SymbolTable::new(&self.global_symbols)
     }
@@ -147,6 +348,735 @@ impl Environment {
     }
 }
 
+/// Snapshotting a fully-populated `Environment` (default units + user definitions + constants) to
+/// a compact binary format, so it can be saved once and reloaded instead of rebuilt via
+/// `add_default_units`/`add_default_symbols` on every `new()`. Every length-prefixed collection is
+/// a little-endian `u32` count followed by that many entries, and every primitive is written
+/// little-endian, so the format is stable across platforms.
+impl Environment {
+    pub fn serialize(&self, out: &mut Vec<u8>) {
+        write_u32(out, self.unit_classes.len() as u32);
+        for (_, unit_class) in self.unit_classes.iter() {
+            write_string(out, &unit_class.0);
+        }
+
+        write_u32(out, self.units.len() as u32);
+        for (_, unit) in self.units.iter() {
+            serialize_unit(out, unit);
+        }
+
+        write_u32(out, self.global_symbols.len() as u32);
+        for (symbol, value) in self.global_symbols.iter() {
+            write_string(out, &symbol.0);
+            serialize_value(out, value);
+        }
+    }
+
+    /// Rebuilds an `Environment` from bytes written by `serialize`. `unit_classes` and `units`
+    /// are reloaded in the same order they were written, so every `Id<UnitClass>`/`Id<Unit>`
+    /// embedded in a serialized unit or global symbol still points at the right entry.
+    /// `derived_units` isn't serialized, since it's just a cache recomputed from `units`.
+    pub fn deserialize(data: &[u8]) -> Self {
+        let mut cursor = 0;
+
+        let mut unit_classes = ItemStorage::new();
+        for _ in 0..read_u32(data, &mut cursor) {
+            unit_classes.insert(UnitClass(read_string(data, &mut cursor)));
+        }
+
+        let mut units = ItemStorage::new();
+        for _ in 0..read_u32(data, &mut cursor) {
+            units.insert(deserialize_unit(data, &mut cursor));
+        }
+
+        let mut global_symbols = HashMap::new();
+        for _ in 0..read_u32(data, &mut cursor) {
+            let symbol = Symbol(read_string(data, &mut cursor));
+            let value = deserialize_value(data, &mut cursor);
+            global_symbols.insert(symbol, value);
+        }
+
+        let mut result = Self {
+            unit_classes,
+            units,
+            global_symbols,
+            derived_units: HashMap::new(),
+            formatting_style: FormattingStyle::Auto,
+            base: Base::Decimal,
+        };
+        result.register_derived_units();
+        result
+    }
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_i32(out: &mut Vec<u8>, value: i32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_f64(out: &mut Vec<u8>, value: f64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_bool(out: &mut Vec<u8>, value: bool) {
+    out.push(value as u8);
+}
+
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    write_u32(out, value.len() as u32);
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn read_u32(data: &[u8], cursor: &mut usize) -> u32 {
+    let value = u32::from_le_bytes(data[*cursor..*cursor + 4].try_into().unwrap());
+    *cursor += 4;
+    value
+}
+
+fn read_i32(data: &[u8], cursor: &mut usize) -> i32 {
+    let value = i32::from_le_bytes(data[*cursor..*cursor + 4].try_into().unwrap());
+    *cursor += 4;
+    value
+}
+
+fn read_f64(data: &[u8], cursor: &mut usize) -> f64 {
+    let value = f64::from_le_bytes(data[*cursor..*cursor + 8].try_into().unwrap());
+    *cursor += 8;
+    value
+}
+
+fn read_bool(data: &[u8], cursor: &mut usize) -> bool {
+    let value = data[*cursor] != 0;
+    *cursor += 1;
+    value
+}
+
+fn read_string(data: &[u8], cursor: &mut usize) -> String {
+    let len = read_u32(data, cursor) as usize;
+    let value =
+        String::from_utf8(data[*cursor..*cursor + len].to_vec()).expect("corrupt serialized string");
+    *cursor += len;
+    value
+}
+
+fn serialize_composite_unit_class(out: &mut Vec<u8>, unit_class: &CompositeUnitClass) {
+    write_u32(out, unit_class.components.len() as u32);
+    for (unit_class_id, power) in unit_class.components.iter() {
+        write_u32(out, unit_class_id.index() as u32);
+        write_i32(out, *power);
+    }
+}
+
+fn deserialize_composite_unit_class(data: &[u8], cursor: &mut usize) -> CompositeUnitClass {
+    let mut components = Vec::new();
+    for _ in 0..read_u32(data, cursor) {
+        let id = Id::from_index(read_u32(data, cursor) as usize);
+        let power = read_i32(data, cursor);
+        components.push((id, power));
+    }
+    CompositeUnitClass { components }
+}
+
+fn serialize_composite_unit(out: &mut Vec<u8>, unit: &CompositeUnit) {
+    write_u32(out, unit.components.len() as u32);
+    for (unit_id, power) in unit.components.iter() {
+        write_u32(out, unit_id.index() as u32);
+        write_i32(out, *power);
+    }
+}
+
+fn deserialize_composite_unit(data: &[u8], cursor: &mut usize) -> CompositeUnit {
+    let mut components = Vec::new();
+    for _ in 0..read_u32(data, cursor) {
+        let id = Id::from_index(read_u32(data, cursor) as usize);
+        let power = read_i32(data, cursor);
+        components.push((id, power));
+    }
+    CompositeUnit { components }
+}
+
+fn serialize_unit(out: &mut Vec<u8>, unit: &Unit) {
+    write_string(out, &unit.name);
+    serialize_composite_unit_class(out, &unit.base_class);
+    write_f64(out, unit.base_ratio);
+    write_bool(out, unit.is_named_derived_unit);
+    write_bool(out, unit.log_conversion.is_some());
+    if let Some(log) = unit.log_conversion {
+        write_f64(out, log.base);
+        write_f64(out, log.coefficient);
+    }
+}
+
+fn deserialize_unit(data: &[u8], cursor: &mut usize) -> Unit {
+    let name = read_string(data, cursor);
+    let base_class = deserialize_composite_unit_class(data, cursor);
+    let base_ratio = read_f64(data, cursor);
+    let is_named_derived_unit = read_bool(data, cursor);
+    let log_conversion = if read_bool(data, cursor) {
+        Some(LogConversion {
+            base: read_f64(data, cursor),
+            coefficient: read_f64(data, cursor),
+        })
+    } else {
+        None
+    };
+    Unit {
+        name,
+        base_class,
+        base_ratio,
+        is_named_derived_unit,
+        log_conversion,
+    }
+}
+
+fn serialize_scalar(out: &mut Vec<u8>, scalar: &Scalar) {
+    write_f64(out, scalar.base_value);
+    write_f64(out, scalar.uncertainty);
+    serialize_composite_unit_class(out, &scalar.base_unit);
+    serialize_composite_unit(out, &scalar.display_unit);
+    write_u32(out, scalar.precision);
+}
+
+fn deserialize_scalar(data: &[u8], cursor: &mut usize) -> Scalar {
+    let base_value = read_f64(data, cursor);
+    let uncertainty = read_f64(data, cursor);
+    let base_unit = deserialize_composite_unit_class(data, cursor);
+    let display_unit = deserialize_composite_unit(data, cursor);
+    let precision = read_u32(data, cursor);
+    Scalar::new_with_uncertainty(base_value, uncertainty, base_unit, display_unit, precision)
+}
+
+fn serialize_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Scalar(scalar) => {
+            write_u32(out, 0);
+            serialize_scalar(out, scalar);
+        }
+        Value::Vector => write_u32(out, 1),
+    }
+}
+
+fn deserialize_value(data: &[u8], cursor: &mut usize) -> Value {
+    match read_u32(data, cursor) {
+        0 => Value::Scalar(deserialize_scalar(data, cursor)),
+        1 => Value::Vector,
+        tag => panic!("corrupt serialized value tag {}", tag),
+    }
+}
+
+/// Expressing a dimensional signature as combinations of named units, e.g. "what units give me
+/// this dimensionality?".
+impl Environment {
+    /// Factorizations using more than this many unit factors (counting repeats) are pruned, so
+    /// the search terminates even when `target` cannot be reached from the registered units.
+    const MAX_FACTORIZE_EXPONENT: i32 = 6;
+
+    /// How many times a single dimensional remainder may be expanded before it's pruned. This
+    /// bounds the search (the same remainder can otherwise be reached via unboundedly many unit
+    /// orderings) while still letting a handful of distinct paths through each remainder survive,
+    /// so `factorize` returns a ranked set of candidates rather than collapsing to one.
+    const MAX_EXPANSIONS_PER_REMAINING: u32 = 4;
+
+    /// Searches products/quotients of registered units for composites whose base unit class
+    /// equals `target`, returning candidates ranked best-first by score (distinct units used plus
+    /// total absolute exponent, lowest first). `base_unit_of(result) == target` holds for every
+    /// returned composite. Returns a single unitless composite for an unitless target, and an
+    /// empty vec if no factorization survives the depth bound and the distance pruning (see
+    /// `dimensional_distance`).
+    pub fn factorize(&self, target: &CompositeUnitClass) -> Vec<CompositeUnit> {
+        if target.components.is_empty() {
+            return vec![CompositeUnit::unitless()];
+        }
+
+        let unit_ids: Vec<Id<Unit>> = self.units.iter().map(|(id, _)| id).collect();
+
+        let mut frontier = BinaryHeap::new();
+        frontier.push(FactorizeCandidate {
+            composite: CompositeUnit::unitless(),
+            remaining: target.clone(),
+            distinct_units: 0,
+            total_exponent: 0,
+        });
+
+        let mut seen = HashSet::new();
+        // How many times each dimensional remainder has been expanded so far. The heap pops in
+        // non-decreasing score order, so capping this (rather than closing a remainder off after
+        // its first expansion) still bounds the search while letting several different named-unit
+        // combinations that happen to reach the same remainder each continue being explored and
+        // ranked, instead of only the single cheapest path to it surviving.
+        let mut expansions_per_remaining = HashMap::new();
+        let mut results = Vec::new();
+        while let Some(candidate) = frontier.pop() {
+            if candidate.remaining.components.is_empty() {
+                if seen.insert(candidate.composite.clone()) {
+                    results.push(candidate.composite.clone());
+                }
+                continue;
+            }
+            let expansions = expansions_per_remaining
+                .entry(candidate.remaining.clone())
+                .or_insert(0);
+            if *expansions >= Self::MAX_EXPANSIONS_PER_REMAINING {
+                continue;
+            }
+            *expansions += 1;
+            if candidate.total_exponent >= Self::MAX_FACTORIZE_EXPONENT {
+                continue;
+            }
+            for &unit_id in &unit_ids {
+                let unit_class = self.borrow(unit_id).base_class.clone();
+                for power in [1, -1] {
+                    let next_remaining = if power > 0 {
+                        candidate.remaining.clone() / unit_class.clone()
+                    } else {
+                        candidate.remaining.clone() * unit_class.clone()
+                    };
+                    let budget_left = Self::MAX_FACTORIZE_EXPONENT - candidate.total_exponent - 1;
+                    if Self::dimensional_distance(&next_remaining) > budget_left {
+                        continue;
+                    }
+                    let next_composite =
+                        candidate.composite.clone() * CompositeUnit::single(unit_id, power);
+                    let (distinct_units, total_exponent) = Self::factorize_score(&next_composite);
+                    frontier.push(FactorizeCandidate {
+                        composite: next_composite,
+                        remaining: next_remaining,
+                        distinct_units,
+                        total_exponent,
+                    });
+                }
+            }
+        }
+
+        results.sort_by_key(|composite| {
+            let (distinct_units, total_exponent) = Self::factorize_score(composite);
+            distinct_units + total_exponent
+        });
+        results
+    }
+
+    /// `(distinct named units used, total absolute exponent)` for `composite`, the two terms the
+    /// heuristic score (their sum) is built from.
+    fn factorize_score(composite: &CompositeUnit) -> (i32, i32) {
+        let distinct_units = composite.components.len() as i32;
+        let total_exponent: i32 = composite.components.iter().map(|(_, power)| power.abs()).sum();
+        (distinct_units, total_exponent)
+    }
+
+    /// Sum of the absolute exponent of every component of `remaining`. A branch whose distance
+    /// exceeds its remaining depth budget gets pruned. This is a strict lower bound on the unit
+    /// factors still needed only when each unit covers a single unit class: a unit like `N`
+    /// cancels several exponents at once, so some branches that could still reach zero within
+    /// the budget (e.g. a target of `N^3`) are pruned as well.
+    fn dimensional_distance(remaining: &CompositeUnitClass) -> i32 {
+        remaining.components.iter().map(|(_, power)| power.abs()).sum()
+    }
+}
+
+/// One partial factorization in `Environment::factorize`'s best-first search, ordered so that
+/// `BinaryHeap` (a max-heap) pops the lowest-scoring (best) candidate first.
+#[derive(Clone, Debug)]
+struct FactorizeCandidate {
+    composite: CompositeUnit,
+    remaining: CompositeUnitClass,
+    distinct_units: i32,
+    total_exponent: i32,
+}
+
+impl FactorizeCandidate {
+    fn score(&self) -> i32 {
+        self.distinct_units + self.total_exponent
+    }
+}
+
+impl PartialEq for FactorizeCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.score() == other.score()
+    }
+}
+
+impl Eq for FactorizeCandidate {}
+
+impl PartialOrd for FactorizeCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FactorizeCandidate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.score().cmp(&self.score())
+    }
+}
+
+/// A single SI decimal or IEC binary prefix, e.g. kilo (1e3) or mebi (2^20).
+#[derive(Clone, Copy, Debug)]
+struct Prefix {
+    symbol: &'static str,
+    multiplier: f64,
+}
+
+/// SI decimal prefixes, ordered from yotta down to yocto. `ALL_PREFIXES` re-sorts them
+/// longest-symbol-first so `da` (deka) is tried before `d` (deci) and never mistaken for it.
+const SI_PREFIXES: &[Prefix] = &[
+    Prefix { symbol: "Y", multiplier: 1e24 },
+    Prefix { symbol: "Z", multiplier: 1e21 },
+    Prefix { symbol: "E", multiplier: 1e18 },
+    Prefix { symbol: "P", multiplier: 1e15 },
+    Prefix { symbol: "T", multiplier: 1e12 },
+    Prefix { symbol: "G", multiplier: 1e9 },
+    Prefix { symbol: "M", multiplier: 1e6 },
+    Prefix { symbol: "k", multiplier: 1e3 },
+    Prefix { symbol: "h", multiplier: 1e2 },
+    Prefix { symbol: "da", multiplier: 1e1 },
+    Prefix { symbol: "d", multiplier: 1e-1 },
+    Prefix { symbol: "c", multiplier: 1e-2 },
+    Prefix { symbol: "m", multiplier: 1e-3 },
+    Prefix { symbol: "u", multiplier: 1e-6 },
+    Prefix { symbol: "n", multiplier: 1e-9 },
+    Prefix { symbol: "p", multiplier: 1e-12 },
+    Prefix { symbol: "f", multiplier: 1e-15 },
+    Prefix { symbol: "a", multiplier: 1e-18 },
+    Prefix { symbol: "z", multiplier: 1e-21 },
+    Prefix { symbol: "y", multiplier: 1e-24 },
+];
+
+/// IEC binary prefixes, kibi (2^10) through yobi (2^80).
+const BINARY_PREFIXES: &[Prefix] = &[
+    Prefix { symbol: "Ki", multiplier: (1u128 << 10) as f64 },
+    Prefix { symbol: "Mi", multiplier: (1u128 << 20) as f64 },
+    Prefix { symbol: "Gi", multiplier: (1u128 << 30) as f64 },
+    Prefix { symbol: "Ti", multiplier: (1u128 << 40) as f64 },
+    Prefix { symbol: "Pi", multiplier: (1u128 << 50) as f64 },
+    Prefix { symbol: "Ei", multiplier: (1u128 << 60) as f64 },
+    Prefix { symbol: "Zi", multiplier: (1u128 << 70) as f64 },
+    Prefix { symbol: "Yi", multiplier: (1u128 << 80) as f64 },
+];
+
+/// `SI_PREFIXES` and `BINARY_PREFIXES` concatenated and pre-sorted longest-symbol-first, so
+/// `resolve_unit_name` doesn't have to re-collect and re-sort a combined list on every call.
+static ALL_PREFIXES: std::sync::LazyLock<Vec<Prefix>> = std::sync::LazyLock::new(|| {
+    let mut prefixes: Vec<Prefix> = SI_PREFIXES.iter().chain(BINARY_PREFIXES.iter()).copied().collect();
+    prefixes.sort_by_key(|prefix| std::cmp::Reverse(prefix.symbol.len()));
+    prefixes
+});
+
+/// Resolving unit names that aren't directly registered, by stripping a prefix.
+impl Environment {
+    fn find_unit_by_name(&self, name: &str) -> Option<Id<Unit>> {
+        self.units
+            .iter()
+            .find(|(_, unit)| unit.name == name)
+            .map(|(id, _)| id)
+    }
+
+    /// Resolves `name` to a registered unit, or, failing that, to a prefix (SI decimal or IEC
+    /// binary) applied to a registered unit, e.g. `km` resolves to kilo-meter and `Gib` resolves
+    /// to gibi-bit. Prefixes are tried longest-symbol-first so `dam` resolves as deka-meter
+    /// rather than deci + "am", and binary prefixes like `Ki`/`Mi` are never mistaken for a `K`
+    /// or `M` followed by an `i`-prefixed unit. A prefix match is interned into `units` on the
+    /// spot (so the returned `Id` is immediately usable by `make_scalar`, `format_unit`, and
+    /// every other `Id`-based consumer) and subsequent lookups of the same prefixed name find it
+    /// directly via `find_unit_by_name` rather than re-synthesizing and re-inserting a duplicate.
+    /// Returns `None` if no prefix/base-unit split produces a registered unit.
+    pub fn resolve_unit_name(&mut self, name: &str) -> Option<Id<Unit>> {
+        if let Some(id) = self.find_unit_by_name(name) {
+            return Some(id);
+        }
+
+        for prefix in ALL_PREFIXES.iter() {
+            let rest = match name.strip_prefix(prefix.symbol) {
+                Some(rest) if !rest.is_empty() => rest,
+                _ => continue,
+            };
+            if let Some(base_id) = self.find_unit_by_name(rest) {
+                let base = self.borrow(base_id);
+                let synthesized = Unit {
+                    name: format!("{}{}", prefix.symbol, base.name),
+                    base_class: base.base_class.clone(),
+                    base_ratio: base.base_ratio * prefix.multiplier,
+                    is_named_derived_unit: false,
+                    log_conversion: base.log_conversion,
+                };
+                return Some(self.units.insert(synthesized));
+            }
+        }
+        None
+    }
+}
+
+/// Describes a logarithmic unit's conversion to/from its linear base unit:
+/// `value_in_base = base ^ (value * coefficient)`, and its inverse
+/// `value = log_base(value_in_base) / coefficient`. For power-quantity decibels, `base` is 10 and
+/// `coefficient` is `1.0 / 10.0`; for field-quantity decibels (e.g. voltage, amplitude),
+/// `coefficient` is `1.0 / 20.0`. Nepers use `base = std::f64::consts::E` and `coefficient = 1.0`.
+#[derive(Clone, Copy, Debug)]
+pub struct LogConversion {
+    pub base: f64,
+    pub coefficient: f64,
+}
+
+impl LogConversion {
+    fn to_base(&self, value: f64) -> f64 {
+        self.base.powf(value * self.coefficient)
+    }
+
+    fn from_base(&self, base_value: f64) -> f64 {
+        base_value.log(self.base) / self.coefficient
+    }
+}
+
+/// Standard first-order uncertainty propagation rules, expressed on base-unit values so callers
+/// (the arithmetic operator impls on `Scalar`) don't need to worry about unit conversion. These
+/// mirror the usual formulas for combining independent measurement uncertainties.
+/// Absolute uncertainties of a sum/difference combine in quadrature: sqrt(sigma_a^2 + sigma_b^2).
+pub(crate) fn propagate_uncertainty_additive(uncertainty_a: f64, uncertainty_b: f64) -> f64 {
+    (uncertainty_a.powi(2) + uncertainty_b.powi(2)).sqrt()
+}
+
+/// Relative uncertainties of a product/quotient combine in quadrature; the result is the
+/// resulting absolute uncertainty, given the two inputs and the already-computed result.
+pub(crate) fn propagate_uncertainty_multiplicative(
+    value_a: f64,
+    uncertainty_a: f64,
+    value_b: f64,
+    uncertainty_b: f64,
+    result: f64,
+) -> f64 {
+    // A zero-valued factor (exact or measured) has no meaningful relative uncertainty; treat it
+    // as contributing none rather than dividing by zero and poisoning the result with NaN/inf.
+    let relative_a = if value_a == 0.0 { 0.0 } else { uncertainty_a / value_a };
+    let relative_b = if value_b == 0.0 { 0.0 } else { uncertainty_b / value_b };
+    result.abs() * (relative_a.powi(2) + relative_b.powi(2)).sqrt()
+}
+
+/// Raising a value to the power `n` scales its relative uncertainty by `|n|`.
+pub(crate) fn propagate_uncertainty_pow(value: f64, uncertainty: f64, n: f64, result: f64) -> f64 {
+    if value == 0.0 {
+        return 0.0;
+    }
+    result.abs() * (uncertainty / value).abs() * n.abs()
+}
+
+/// Arithmetic on `Scalar`, propagating measurement uncertainty alongside the value.
+impl std::ops::Add for Scalar {
+    type Output = Scalar;
+
+    /// Adding two scalars of the same dimension combines their absolute uncertainties in
+    /// quadrature. Panics if the operands aren't dimensionally compatible, same as the
+    /// underlying numeric addition would be meaningless otherwise.
+    fn add(self, rhs: Scalar) -> Scalar {
+        assert_eq!(
+            self.base_unit, rhs.base_unit,
+            "cannot add scalars of different dimensions"
+        );
+        let base_value = self.base_value + rhs.base_value;
+        let uncertainty = propagate_uncertainty_additive(self.uncertainty, rhs.uncertainty);
+        Scalar::new_with_uncertainty(
+            base_value,
+            uncertainty,
+            self.base_unit,
+            self.display_unit,
+            self.precision.min(rhs.precision),
+        )
+    }
+}
+
+impl std::ops::Sub for Scalar {
+    type Output = Scalar;
+
+    /// Subtracting two scalars of the same dimension combines their absolute uncertainties in
+    /// quadrature, same as addition.
+    fn sub(self, rhs: Scalar) -> Scalar {
+        assert_eq!(
+            self.base_unit, rhs.base_unit,
+            "cannot subtract scalars of different dimensions"
+        );
+        let base_value = self.base_value - rhs.base_value;
+        let uncertainty = propagate_uncertainty_additive(self.uncertainty, rhs.uncertainty);
+        Scalar::new_with_uncertainty(
+            base_value,
+            uncertainty,
+            self.base_unit,
+            self.display_unit,
+            self.precision.min(rhs.precision),
+        )
+    }
+}
+
+impl std::ops::Mul for Scalar {
+    type Output = Scalar;
+
+    /// Multiplying two scalars combines their relative uncertainties in quadrature.
+    fn mul(self, rhs: Scalar) -> Scalar {
+        let base_value = self.base_value * rhs.base_value;
+        let uncertainty = propagate_uncertainty_multiplicative(
+            self.base_value,
+            self.uncertainty,
+            rhs.base_value,
+            rhs.uncertainty,
+            base_value,
+        );
+        Scalar::new_with_uncertainty(
+            base_value,
+            uncertainty,
+            self.base_unit * rhs.base_unit,
+            self.display_unit * rhs.display_unit,
+            self.precision.min(rhs.precision),
+        )
+    }
+}
+
+impl std::ops::Div for Scalar {
+    type Output = Scalar;
+
+    /// Dividing two scalars combines their relative uncertainties in quadrature, same as
+    /// multiplication.
+    fn div(self, rhs: Scalar) -> Scalar {
+        let base_value = self.base_value / rhs.base_value;
+        let uncertainty = propagate_uncertainty_multiplicative(
+            self.base_value,
+            self.uncertainty,
+            rhs.base_value,
+            rhs.uncertainty,
+            base_value,
+        );
+        Scalar::new_with_uncertainty(
+            base_value,
+            uncertainty,
+            self.base_unit / rhs.base_unit,
+            self.display_unit / rhs.display_unit,
+            self.precision.min(rhs.precision),
+        )
+    }
+}
+
+impl Scalar {
+    /// Raises a scalar to an integer power, scaling its relative uncertainty by `|n|`.
+    pub fn powi(self, n: i32) -> Scalar {
+        let base_value = self.base_value.powi(n);
+        let uncertainty =
+            propagate_uncertainty_pow(self.base_value, self.uncertainty, n as f64, base_value);
+        let mut base_unit = CompositeUnitClass::unitless();
+        let mut display_unit = CompositeUnit::unitless();
+        for _ in 0..n.abs() {
+            if n > 0 {
+                base_unit = base_unit * self.base_unit.clone();
+                display_unit = display_unit * self.display_unit.clone();
+            } else {
+                base_unit = base_unit / self.base_unit.clone();
+                display_unit = display_unit / self.display_unit.clone();
+            }
+        }
+        Scalar::new_with_uncertainty(base_value, uncertainty, base_unit, display_unit, self.precision)
+    }
+}
+
+/// Derives a sensible displayed precision (number of significant figures) from a value and its
+/// uncertainty, by rounding the value to the first significant figure of the uncertainty: e.g. a
+/// value of 9.81 with uncertainty 0.03 should display 3 significant figures (9.81), not whatever
+/// precision the scalar happened to carry.
+fn significant_figure_precision(value: f64, uncertainty: f64) -> u32 {
+    if value == 0.0 || uncertainty <= 0.0 || !uncertainty.is_finite() {
+        return 1;
+    }
+    let value_exponent = value.abs().log10().floor();
+    let uncertainty_exponent = uncertainty.abs().log10().floor();
+    ((value_exponent - uncertainty_exponent) as i32 + 1).max(1) as u32
+}
+
+/// Number of digits after the decimal point that renders `value` with `precision` significant
+/// figures, e.g. `decimal_places(1500.0, 3) == 0` and `decimal_places(0.015, 3) == 4`.
+fn decimal_places(value: f64, precision: u32) -> usize {
+    if value == 0.0 {
+        return (precision.max(1) - 1) as usize;
+    }
+    let exponent = value.abs().log10().floor() as i32;
+    (precision as i32 - 1 - exponent).max(0) as usize
+}
+
+/// Picks the exponent (a multiple of three) engineering notation would display `value` with,
+/// e.g. `1500.0` -> `3` (1.5e3) and `0.025` -> `-3` (25e-3).
+fn engineering_exponent(value: f64) -> i32 {
+    if value == 0.0 {
+        return 0;
+    }
+    let raw_exponent = value.abs().log10().floor() as i32;
+    raw_exponent - raw_exponent.rem_euclid(3)
+}
+
+/// Finds the registered SI decimal prefix whose multiplier is exactly `10^exponent`, if any.
+fn prefix_for_exponent(exponent: i32) -> Option<&'static Prefix> {
+    SI_PREFIXES
+        .iter()
+        .find(|prefix| prefix.multiplier.log10().round() as i32 == exponent)
+}
+
+/// Renders `value` as a signed integer in `base`, rounding to the nearest whole number first
+/// (e.g. for byte counts). Decimal values that aren't integral still get rounded, since there's
+/// no meaningful way to express a fractional value in binary/octal/hex here.
+fn format_integer_in_base(value: f64, base: Base) -> String {
+    let rounded = value.round() as i64;
+    let sign = if rounded < 0 { "-" } else { "" };
+    let magnitude = rounded.unsigned_abs();
+    match base {
+        Base::Binary => format!("{}0b{:b}", sign, magnitude),
+        Base::Octal => format!("{}0o{:o}", sign, magnitude),
+        Base::Decimal => format!("{}{}", sign, magnitude),
+        Base::Hex => format!("{}0x{:X}", sign, magnitude),
+    }
+}
+
+/// Approximates `value` as a fraction using a bounded continued-fraction expansion, e.g. `1/3`
+/// for `0.3333...`. Returns just the integer (e.g. `5`) if `value` is already an integer, and
+/// gives up (returning a decimal string) once a denominator would exceed `MAX_DENOMINATOR`,
+/// rather than producing an absurdly large one. If `MAX_TERMS` terms pass without converging
+/// and without crossing that limit, the last convergent is returned as the fraction.
+fn format_exact_fraction(value: f64) -> String {
+    const MAX_TERMS: u32 = 32;
+    const MAX_DENOMINATOR: f64 = 1e9;
+
+    if value.fract() == 0.0 {
+        return format!("{}", value as i64);
+    }
+
+    let target = value.abs();
+    let sign = if value < 0.0 { "-" } else { "" };
+    let mut x = target;
+    let (mut num_prev, mut num_cur) = (0i64, 1i64);
+    let (mut den_prev, mut den_cur) = (1i64, 0i64);
+
+    for _ in 0..MAX_TERMS {
+        let whole = x.floor();
+        let (num_next, den_next) = (
+            whole as i64 * num_cur + num_prev,
+            whole as i64 * den_cur + den_prev,
+        );
+        num_prev = num_cur;
+        den_prev = den_cur;
+        num_cur = num_next;
+        den_cur = den_next;
+
+        if den_cur as f64 > MAX_DENOMINATOR {
+            return format!("{}", value);
+        }
+        if (num_cur as f64 / den_cur as f64 - target).abs() < 1e-12 {
+            break;
+        }
+
+        let fract = x - whole;
+        if fract.abs() < 1e-12 {
+            break;
+        }
+        x = 1.0 / fract;
+    }
+
+    format!("{}{}/{}", sign, num_cur, den_cur)
+}
+
 impl StorageHolder<UnitClass> for Environment {
     fn borrow_storage(&self) -> &ItemStorage<UnitClass> {
         &self.unit_classes
@@ -166,3 +1096,238 @@ impl StorageHolder<Unit> for Environment {
         &mut self.units
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small hand-built environment (mass/length/time, kg/m/s, a derived Newton, and a
+    /// logarithmic dB unit) so these tests don't depend on `add_default_units`/
+    /// `add_default_symbols` pulling in the rest of the unit table.
+    fn test_environment() -> Environment {
+        let mut unit_classes = ItemStorage::new();
+        let mass = unit_classes.insert(UnitClass("Mass".to_owned()));
+        let length = unit_classes.insert(UnitClass("Length".to_owned()));
+        let time = unit_classes.insert(UnitClass("Time".to_owned()));
+
+        let mut units = ItemStorage::new();
+        units.insert(Unit {
+            name: "kg".to_owned(),
+            base_class: CompositeUnitClass {
+                components: vec![(mass, 1)],
+            },
+            base_ratio: 1.0,
+            is_named_derived_unit: false,
+            log_conversion: None,
+        });
+        units.insert(Unit {
+            name: "m".to_owned(),
+            base_class: CompositeUnitClass {
+                components: vec![(length, 1)],
+            },
+            base_ratio: 1.0,
+            is_named_derived_unit: false,
+            log_conversion: None,
+        });
+        units.insert(Unit {
+            name: "s".to_owned(),
+            base_class: CompositeUnitClass {
+                components: vec![(time, 1)],
+            },
+            base_ratio: 1.0,
+            is_named_derived_unit: false,
+            log_conversion: None,
+        });
+        units.insert(Unit {
+            name: "N".to_owned(),
+            base_class: CompositeUnitClass {
+                components: vec![(mass, 1), (length, 1), (time, -2)],
+            },
+            base_ratio: 1.0,
+            is_named_derived_unit: true,
+            log_conversion: None,
+        });
+        units.insert(Unit {
+            name: "dB".to_owned(),
+            base_class: CompositeUnitClass::unitless(),
+            base_ratio: 1.0,
+            is_named_derived_unit: false,
+            log_conversion: Some(LogConversion {
+                base: 10.0,
+                coefficient: 0.1,
+            }),
+        });
+
+        let mut env = Environment {
+            unit_classes,
+            units,
+            global_symbols: HashMap::new(),
+            derived_units: HashMap::new(),
+            formatting_style: FormattingStyle::Auto,
+            base: Base::Decimal,
+        };
+        env.register_derived_units();
+        env
+    }
+
+    fn direct_unit(env: &mut Environment, name: &str) -> CompositeUnit {
+        match env.resolve_unit_name(name) {
+            Some(id) => CompositeUnit {
+                components: vec![(id, 1)],
+            },
+            None => panic!("expected {} to resolve to a registered unit", name),
+        }
+    }
+
+    #[test]
+    fn environment_serialize_roundtrip_preserves_formatting() {
+        let mut env = test_environment();
+        let unit = direct_unit(&mut env, "kg");
+        let scalar = env.make_scalar(5.0, unit, 4);
+        let before = env.format_scalar_detailed(&scalar);
+
+        let mut bytes = Vec::new();
+        env.serialize(&mut bytes);
+        let mut restored_env = Environment::deserialize(&bytes);
+
+        let restored_unit = direct_unit(&mut restored_env, "kg");
+        let restored_scalar = restored_env.make_scalar(5.0, restored_unit, 4);
+        let after = restored_env.format_scalar_detailed(&restored_scalar);
+
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn scalar_serialize_roundtrip_is_identical() {
+        let mut env = test_environment();
+        let unit = direct_unit(&mut env, "kg");
+        let scalar = env.make_scalar_with_uncertainty(2.5, unit, 3, 0.1);
+
+        let mut bytes = Vec::new();
+        serialize_scalar(&mut bytes, &scalar);
+        let mut cursor = 0;
+        let restored = deserialize_scalar(&bytes, &mut cursor);
+
+        assert_eq!(
+            env.format_scalar_detailed(&scalar),
+            env.format_scalar_detailed(&restored)
+        );
+    }
+
+    #[test]
+    fn log_unit_roundtrip_is_identity() {
+        let mut env = test_environment();
+        let db = direct_unit(&mut env, "dB");
+        let log = env
+            .log_conversion_of(&db)
+            .expect("dB should carry a log conversion");
+
+        let original = 20.0;
+        let roundtripped = log.from_base(log.to_base(original));
+        assert!((roundtripped - original).abs() < 1e-9);
+
+        // And through the full Environment::make_scalar / format_scalar_detailed path: the
+        // displayed value should come back out at the original, not the linear base value.
+        let scalar = env.make_scalar(original, db, 6);
+        assert!(env.format_scalar_detailed(&scalar).contains("2.00000e1"));
+    }
+
+    #[test]
+    fn factorize_dedups_and_ranks_best_first() {
+        let env = test_environment();
+        let newton = env.find_unit_by_name("N").expect("N registered");
+        let target = env.borrow(newton).base_class.clone();
+
+        let results = env.factorize(&target);
+        assert!(!results.is_empty());
+        // Both `N` itself and the `kg*m*s^-2` expansion should come back, not just the first
+        // path the search happens to reach the target dimension through.
+        assert!(
+            results.len() >= 2,
+            "expected multiple distinct factorizations, got {:?}",
+            results
+        );
+
+        let mut seen = HashSet::new();
+        for composite in &results {
+            assert!(
+                seen.insert(composite.clone()),
+                "factorize returned a duplicate factorization"
+            );
+            assert_eq!(
+                env.base_unit_of(composite),
+                target,
+                "factorize returned a result with the wrong dimensional signature"
+            );
+        }
+
+        let scores: Vec<i32> = results
+            .iter()
+            .map(|composite| {
+                let distinct_units = composite.components.len() as i32;
+                let total_exponent: i32 =
+                    composite.components.iter().map(|(_, power)| power.abs()).sum();
+                distinct_units + total_exponent
+            })
+            .collect();
+        assert!(
+            scores.windows(2).all(|pair| pair[0] <= pair[1]),
+            "factorize results weren't sorted best-first: {:?}",
+            scores
+        );
+    }
+
+    #[test]
+    fn factorize_unreachable_target_returns_empty() {
+        let env = test_environment();
+        // Mass raised to one more than MAX_FACTORIZE_EXPONENT can't be reached by any combination
+        // of kg/m/s/N within the exponent bound, so the search should exhaust its frontier and
+        // come back empty rather than hang or panic.
+        let mass_only = CompositeUnitClass {
+            components: vec![(
+                env.unit_classes.iter().next().unwrap().0,
+                Environment::MAX_FACTORIZE_EXPONENT + 1,
+            )],
+        };
+        assert!(env.factorize(&mass_only).is_empty());
+    }
+
+    #[test]
+    fn format_exact_fraction_matches_the_value_not_its_reciprocal() {
+        assert_eq!(format_exact_fraction(0.5), "1/2");
+        assert_eq!(format_exact_fraction(1.0 / 3.0), "1/3");
+        assert_eq!(format_exact_fraction(0.25), "1/4");
+        assert_eq!(format_exact_fraction(0.2), "1/5");
+        assert_eq!(format_exact_fraction(2.5), "5/2");
+        assert_eq!(format_exact_fraction(-0.5), "-1/2");
+    }
+
+    #[test]
+    fn propagate_uncertainty_multiplicative_handles_a_zero_factor() {
+        // 0 m +/- 0.1 m times an exact 5 s shouldn't produce NaN.
+        let uncertainty = propagate_uncertainty_multiplicative(0.0, 0.1, 5.0, 0.0, 0.0);
+        assert_eq!(uncertainty, 0.0);
+
+        let uncertainty = propagate_uncertainty_multiplicative(5.0, 0.0, 0.0, 0.1, 0.0);
+        assert_eq!(uncertainty, 0.0);
+    }
+
+    #[test]
+    fn resolve_unit_name_interns_a_prefixed_unit() {
+        let mut env = test_environment();
+        let kg = env.find_unit_by_name("kg").expect("kg registered");
+
+        let km = env
+            .resolve_unit_name("km")
+            .expect("km should resolve via the k- prefix");
+        assert_ne!(km, kg, "a prefixed unit must be its own interned Unit");
+        assert_eq!(env.borrow(km).base_ratio, env.borrow(kg).base_ratio * 1e3);
+
+        // Resolving the same prefixed name again must find the unit just interned, not
+        // synthesize and insert a second copy.
+        let km_again = env
+            .resolve_unit_name("km")
+            .expect("km should still resolve");
+        assert_eq!(km, km_again);
+    }
+}